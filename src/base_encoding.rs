@@ -0,0 +1,95 @@
+//! RFC 4648 base32 and base64 encoding.
+//!
+//! These are the plain alphabets from the RFC, with no URL-safe or
+//! unpadded variants -- just enough to render a character's UTF-8 bytes
+//! as a copy-pasteable string.
+
+const BASE64_ALPHABET: &'static [u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const BASE32_ALPHABET: &'static [u8; 32] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encodes a byte slice using the RFC 4648 base64 alphabet, packing the
+/// input into 6-bit groups and padding the output with `=` to a multiple
+/// of four characters.
+pub fn base64(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0u32;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+
+        while bits >= 6 {
+            bits -= 6;
+            out.push(BASE64_ALPHABET[((buffer >> bits) & 0x3f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        out.push(BASE64_ALPHABET[((buffer << (6 - bits)) & 0x3f) as usize] as char);
+    }
+
+    while out.len() % 4 != 0 {
+        out.push('=');
+    }
+
+    out
+}
+
+/// Encodes a byte slice using the RFC 4648 base32 alphabet, packing the
+/// input into 5-bit groups and padding the output with `=` to a multiple
+/// of eight characters.
+pub fn base32(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let mut buffer: u64 = 0;
+    let mut bits = 0u32;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u64;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    while out.len() % 8 != 0 {
+        out.push('=');
+    }
+
+    out
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn base64_rfc_vectors() {
+        assert_eq!(base64(b"f"),   "Zg==");
+        assert_eq!(base64(b"fo"),  "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+    }
+
+    #[test]
+    fn base32_rfc_vectors() {
+        assert_eq!(base32(b"f"),   "MY======");
+        assert_eq!(base32(b"fo"),  "MZXQ====");
+        assert_eq!(base32(b"foo"), "MZXW6===");
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base32(b""), "");
+    }
+}
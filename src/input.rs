@@ -0,0 +1,76 @@
+//! Where a `CharInfo` reads its characters from.
+
+use std::fs::File;
+use std::io;
+use std::io::Read;
+
+/// A source of characters, covering the two places `charm` can read from:
+/// standard input, or a file named on the command line.
+///
+/// Borrowing the `io::Stdin` handle for the `'a` lifetime lets us lock it
+/// once up front and hold that lock for the whole run, rather than letting
+/// `Stdin` re-lock on every character read.
+pub enum CharSource<'a> {
+    Stdin(io::Chars<io::StdinLock<'a>>),
+    File(io::Chars<File>),
+}
+
+impl<'a> CharSource<'a> {
+
+    /// Opens the given file, or locks the given standard input handle and
+    /// reads from that if no file name was given.
+    pub fn open(stdin: &'a io::Stdin, input_file_name: Option<&str>) -> io::Result<CharSource<'a>> {
+        match input_file_name {
+            Some(path)  => File::open(path).map(|f| CharSource::File(f.chars())),
+            None        => Ok(CharSource::Stdin(stdin.lock().chars())),
+        }
+    }
+}
+
+impl<'a> Iterator for CharSource<'a> {
+    type Item = Result<char, io::CharsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self {
+            CharSource::Stdin(ref mut chars) => chars.next(),
+            CharSource::File(ref mut chars)  => chars.next(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::io::Write;
+
+    #[test]
+    fn nonexistent_file_is_an_error() {
+        let stdin = io::stdin();
+        let result = CharSource::open(&stdin, Some("/nonexistent/path/used/by/charm/tests"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn file_yields_its_characters() {
+        let mut path = env::temp_dir();
+        path.push("charm-input-test.txt");
+
+        {
+            let mut file = File::create(&path).unwrap();
+            file.write_all("ab".as_bytes()).unwrap();
+        }
+
+        let stdin = io::stdin();
+        let chars: Vec<char> = CharSource::open(&stdin, Some(path.to_str().unwrap()))
+            .unwrap()
+            .map(|c| c.unwrap())
+            .collect();
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(chars, vec!['a', 'b']);
+    }
+}
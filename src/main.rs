@@ -7,23 +7,53 @@ extern crate unicode_names;
 extern crate unicode_width;
 use unicode_width::UnicodeWidthChar;
 
-use std::io;
-use std::io::Read;
 use std::env;
 use std::fmt;
+use std::io;
+
+mod options;
+use options::{Options, OptionsResult, STRICT_ENV_VAR};
+
+mod scripts;
+use scripts::Script;
+
+mod base_encoding;
+
+mod input;
+use input::CharSource;
 
 
 fn main() {
-    let args: Vec<_> = env::args().collect();
-    match Options::getopts(&args[..]) {
-        Ok(options)   => {
-            let thing = io::stdin();
-            let stdin = thing.lock().chars();
-            CharInfo::new(options, stdin).run();
+    let mut args: Vec<_> = env::args().collect();
+
+    // Strict mode can be requested either on the command line or through
+    // the environment; folding the latter in here keeps `Options::parse`
+    // itself free of I/O.
+    if env::var_os(STRICT_ENV_VAR).is_some() {
+        args.push("--strict".to_string());
+    }
+
+    match Options::parse(&args[1..]) {
+        OptionsResult::Ok(options, _free) => {
+            let stdin = io::stdin();
+            match CharSource::open(&stdin, options.input_file_name.as_ref().map(|s| &s[..])) {
+                Ok(source) => CharInfo::new(options, source).run(),
+                Err(e)     => {
+                    println!("charm: {}", e);
+                    env::set_exit_status(1);
+                },
+            }
+        },
+        OptionsResult::Help(text) => {
+            println!("{}", text);
+            env::set_exit_status(2);
+        },
+        OptionsResult::Version => {
+            println!("charm {}", env!("CARGO_PKG_VERSION"));
         },
-        Err(misfire)  => {
-            println!("{}", misfire);
-            env::set_exit_status(misfire.exit_status());
+        OptionsResult::InvalidOptions(e) => {
+            println!("{}", e);
+            env::set_exit_status(e.exit_status());
         },
     }
 }
@@ -43,7 +73,7 @@ impl<I, E> CharInfo<I>
 
     fn new(options: Options, iterator: I) -> CharInfo<I> {
         CharInfo {
-            count:    if options.bytes { 0 } else { 1 },
+            count:    if options.flags.bytes { 0 } else { 1 },
             options:  options,
             input:    iterator,
         }
@@ -65,17 +95,33 @@ impl<I, E> CharInfo<I>
 
                     print!("{:>5}: {} = {}", self.count, CharDisplay(c), NumDisplay(c));
 
-                    if self.options.show_names {
+                    if self.options.flags.show_base64 {
+                        print!(" = {}", Base64Display(c));
+                    }
+
+                    if self.options.flags.show_base32 {
+                        print!(" = {}", Base32Display(c));
+                    }
+
+                    if self.options.flags.show_names {
                         if let Some(name) = unicode_names::name(c) {
                             print!(" ({})", name);
                         }
                     }
 
+                    if self.options.flags.show_scripts {
+                        print!(" [{}]", Script::of(c));
+                    }
+
+                    if self.options.flags.show_widths {
+                        print!(" <{}>", WidthDisplay(c));
+                    }
+
                     if char_type != CharType::Normal {
                         t.reset().unwrap();
                     }
 
-                    self.count += if self.options.bytes { c.len_utf8() as u64 }
+                    self.count += if self.options.flags.bytes { c.len_utf8() as u64 }
                                                                   else { 1u64 };
                     print!("\n");
                 },
@@ -89,65 +135,6 @@ impl<I, E> CharInfo<I>
 
 
 
-struct Options {
-    bytes:       bool,
-    show_names:  bool,
-}
-
-impl Options {
-    pub fn getopts(args: &[String]) -> Result<Options, Misfire> {
-        let mut opts = getopts::Options::new();
-        opts.optflag("b", "bytes",     "show count in number of bytes, not characters");
-        opts.optflag("n", "names",     "show unicode name of each character");
-        opts.optflag("",  "version",   "display version of program");
-        opts.optflag("?", "help",      "show list of command-line options");
-
-        let matches = match opts.parse(args) {
-            Ok(m) => m,
-            Err(e) => return Err(Misfire::InvalidOptions(e)),
-        };
-
-        if matches.opt_present("help") {
-            return Err(Misfire::Help(opts.usage("Usage:\n  charinfo [options] < file")))
-        }
-        else if matches.opt_present("version") {
-            return Err(Misfire::Version);
-        }
-
-        Ok(Options {
-            bytes:       matches.opt_present("bytes"),
-            show_names:  matches.opt_present("names"),
-        })
-    }
-}
-
-
-
-enum Misfire {
-    InvalidOptions(getopts::Fail),
-    Help(String),
-    Version,
-}
-
-impl Misfire {
-    pub fn exit_status(&self) -> i32 {
-        if let Misfire::Help(_) = *self { 2 }
-                                   else { 3 }
-    }
-}
-
-impl fmt::Display for Misfire {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Misfire::InvalidOptions(ref e) => write!(f, "{}", e),
-            Misfire::Help(ref text)        => write!(f, "{}", text),
-            Misfire::Version               => write!(f, "charinfo {}", env!("CARGO_PKG_VERSION")),
-        }
-    }
-}
-
-
-
 #[derive(PartialEq)]
 enum CharType {
     Normal,
@@ -198,6 +185,22 @@ impl fmt::Display for CharDisplay {
 }
 
 
+/// Displays the on-screen width of a character: `0` for control and
+/// combining characters (which draw nothing of their own), otherwise
+/// whatever `UnicodeWidthChar` reports, defaulting to `0` for anything it
+/// doesn't have an opinion on.
+struct WidthDisplay(char);
+
+impl fmt::Display for WidthDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match CharType::of(self.0) {
+            CharType::Control | CharType::Combining => write!(f, "0"),
+            CharType::Normal => write!(f, "{}", UnicodeWidthChar::width(self.0).unwrap_or(0)),
+        }
+    }
+}
+
+
 struct NumDisplay(char);
 
 impl fmt::Display for NumDisplay {
@@ -214,3 +217,25 @@ impl fmt::Display for NumDisplay {
         Ok(())
     }
 }
+
+
+struct Base64Display(char);
+
+impl fmt::Display for Base64Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buffer = [0; 4];  // Four bytes can hold any character
+        let bytes_written = self.0.encode_utf8(&mut buffer).unwrap();
+        write!(f, "{}", base_encoding::base64(&buffer[.. bytes_written]))
+    }
+}
+
+
+struct Base32Display(char);
+
+impl fmt::Display for Base32Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buffer = [0; 4];  // Four bytes can hold any character
+        let bytes_written = self.0.encode_utf8(&mut buffer).unwrap();
+        write!(f, "{}", base_encoding::base32(&buffer[.. bytes_written]))
+    }
+}
@@ -9,6 +9,17 @@ use getopts;
 /// command-line options.
 static USAGE: &'static str = "Usage:\n  charm [options] file";
 
+/// The names of the display flags that may be given more than once by
+/// default. Strict mode checks each of them for repeats.
+static REPEATABLE_FLAGS: &'static [&'static str] =
+    &["bytes", "names", "scripts", "widths", "base64", "base32"];
+
+/// The name of the environment variable that turns on strict mode, as an
+/// alternative to passing `--strict` every time (handy for setting in a
+/// shell alias). It's `main`'s job to read this, not `Options::parse`'s:
+/// see the note on `parse` below.
+pub static STRICT_ENV_VAR: &'static str = "CHARM_STRICT";
+
 /// The **Options** struct represents a parsed version of the user's
 /// command-line options.
 #[derive(PartialEq, Debug)]
@@ -28,31 +39,75 @@ pub struct Flags {
     pub show_names:      bool,
     pub show_scripts:    bool,
     pub show_widths:     bool,
+    pub show_base64:     bool,
+    pub show_base32:     bool,
 }
 
 #[allow(unused_results)]
 impl Options {
 
-    /// Call getopts on the given slice of command-line strings.
-    pub fn getopts(args: &[String]) -> Result<Options, Misfire> {
+    /// Parses a slice of command-line strings into an `OptionsResult`.
+    ///
+    /// This is a pure function: it performs no I/O of its own, which means
+    /// every branch of argument handling -- a successful parse, `--help`,
+    /// `--version`, or an invalid combination of flags -- can be exercised
+    /// directly in a test with nothing but a `Vec<String>`. In particular,
+    /// strict mode is driven entirely by the `--strict` flag in `args`;
+    /// it's the caller's job (see `main.rs`) to fold the `CHARM_STRICT`
+    /// environment variable in before calling this, so that reading the
+    /// environment stays at the edge of the program instead of leaking
+    /// into the thing we want to unit-test.
+    ///
+    /// Giving the same flag more than once is accepted by default, the
+    /// same as running it through a shell alias and then overriding it on
+    /// the command line: booleans just stay set, and the last occurrence
+    /// of a value-bearing option would win. Passing `--strict` turns
+    /// repeated flags into a hard `OptionsError` instead, for callers (CI,
+    /// scripts) that want to catch sloppy invocations rather than
+    /// silently tolerate them.
+    pub fn parse(args: &[String]) -> OptionsResult {
         let mut opts = getopts::Options::new();
         opts.optflag("b", "bytes",     "show count in number of bytes, not characters");
         opts.optflag("n", "names",     "show unicode name of each character");
         opts.optflag("s", "scripts",   "show script for each character");
         opts.optflag("w", "widths",    "show width for each character");
+        opts.optflagmulti("",  "base64",    "show each character's bytes in RFC 4648 base64");
+        opts.optflagmulti("",  "base32",    "show each character's bytes in RFC 4648 base32");
+        opts.optflagmulti("",  "strict",    "treat repeated or redundant options as errors");
         opts.optflag("",  "version",   "display version of program");
         opts.optflag("?", "help",      "show list of command-line options");
 
         let matches = match opts.parse(args) {
             Ok(m)   => m,
-            Err(e)  => return Err(Misfire::InvalidOptions(e)),
+            Err(e)  => return OptionsResult::InvalidOptions(OptionsError::Unparseable(e)),
         };
 
         if matches.opt_present("help") {
-            return Err(Misfire::Help(opts.usage(USAGE)))
+            return OptionsResult::Help(opts.usage(USAGE));
         }
         else if matches.opt_present("version") {
-            return Err(Misfire::Version);
+            return OptionsResult::Version;
+        }
+
+        if matches.opt_present("strict") {
+            for &flag in REPEATABLE_FLAGS {
+                if matches.opt_count(flag) > 1 {
+                    return OptionsResult::InvalidOptions(OptionsError::Duplicate(flag));
+                }
+            }
+        }
+
+        let flags = Flags {
+            bytes:           matches.opt_present("bytes"),
+            show_names:      matches.opt_present("names"),
+            show_scripts:    matches.opt_present("scripts"),
+            show_widths:     matches.opt_present("widths"),
+            show_base64:     matches.opt_present("base64"),
+            show_base32:     matches.opt_present("base32"),
+        };
+
+        if let Err(e) = flags.check_conflicts() {
+            return OptionsResult::InvalidOptions(e);
         }
 
         // The program can read from either standard input *or* it can read
@@ -62,27 +117,45 @@ impl Options {
         let input_file_name = match matches.free.len() {
             0 => None,
             1 => Some(matches.free[0].clone()),
-            _ => return Err(Misfire::Help(opts.usage(USAGE))),
+            _ => return OptionsResult::InvalidOptions(
+                     OptionsError::BadArgument("only one file can be given".to_string())),
         };
 
-        Ok(Options {
-            flags: Flags {
-                bytes:           matches.opt_present("bytes"),
-                show_names:      matches.opt_present("names"),
-                show_scripts:    matches.opt_present("scripts"),
-                show_widths:     matches.opt_present("widths"),
-            },
+        let options = Options {
+            flags: flags,
             input_file_name: input_file_name,
-        })
+        };
+
+        OptionsResult::Ok(options, matches.free)
     }
 }
 
+impl Flags {
 
-/// A thing that could happen instead of running.
-pub enum Misfire {
+    /// Checks for a pointless combination of flags: `--bytes` given without
+    /// any of `--names`, `--scripts`, `--widths`, `--base64` or `--base32`.
+    /// No combination is treated as contradictory.
+    fn check_conflicts(&self) -> Result<(), OptionsError> {
+        if self.bytes && !(self.show_names || self.show_scripts || self.show_widths
+                           || self.show_base64 || self.show_base32) {
+            return Err(OptionsError::Useless("bytes", true,
+                "names, scripts, widths, base64, or base32"));
+        }
 
-    /// The `getopts` crate didn't like these arguments.
-    InvalidOptions(getopts::Fail),
+        Ok(())
+    }
+}
+
+
+/// The result of parsing a set of command-line options: either a usable
+/// `Options` value, something to print instead of running (`--help` or
+/// `--version`), or a reason the arguments couldn't be used at all.
+#[derive(PartialEq, Debug)]
+pub enum OptionsResult {
+
+    /// The options parsed successfully, along with any free (non-flag)
+    /// arguments that were left over, such as a file name.
+    Ok(Options, Vec<String>),
 
     /// The user asked for help. This contains an autogenerated help string
     /// from the `getopts` crate.
@@ -90,23 +163,190 @@ pub enum Misfire {
 
     /// The user wanted the version number.
     Version,
+
+    /// The given command-line arguments could not be turned into a usable
+    /// set of options.
+    InvalidOptions(OptionsError),
 }
 
-impl Misfire {
 
-    /// The OS exit status that this misfire should signify.
+/// Something that can go wrong while validating a set of command-line
+/// arguments, as opposed to running the program itself.
+pub enum OptionsError {
+
+    /// The `getopts` crate didn't like these arguments.
+    Unparseable(getopts::Fail),
+
+    /// An argument was given that doesn't make sense on its own, such as
+    /// more than one input file.
+    BadArgument(String),
+
+    /// An option was given that does nothing unless (`true`) or while
+    /// (`false`) another option is also given.
+    Useless(&'static str, bool, &'static str),
+
+    /// The same flag was given more than once while in strict mode.
+    Duplicate(&'static str),
+}
+
+impl OptionsError {
+
+    /// The OS exit status that this error should signify, distinct from a
+    /// generic "couldn't even parse the arguments" failure so scripts can
+    /// tell the two apart.
     pub fn exit_status(&self) -> i32 {
-        if let Misfire::Help(_) = *self { 2 }
-                                   else { 3 }
+        match *self {
+            OptionsError::Unparseable(_) | OptionsError::BadArgument(_)  => 3,
+            OptionsError::Useless(..)    | OptionsError::Duplicate(_)    => 4,
+        }
     }
 }
 
-impl fmt::Display for Misfire {
+impl fmt::Display for OptionsError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Misfire::InvalidOptions(ref e) => write!(f, "{}", e),
-            Misfire::Help(ref text)        => write!(f, "{}", text),
-            Misfire::Version               => write!(f, "charm {}", env!("CARGO_PKG_VERSION")),
+            OptionsError::Unparseable(ref e)  => write!(f, "{}", e),
+            OptionsError::BadArgument(ref e)  => write!(f, "{}", e),
+            OptionsError::Useless(a, true, b) => write!(f, "Option --{} is useless without option --{}", a, b),
+            OptionsError::Useless(a, false, b) => write!(f, "Option --{} is useless while option --{} is given", a, b),
+            OptionsError::Duplicate(a)        => write!(f, "Option --{} was given more than once (run without --strict to allow this)", a),
+        }
+    }
+}
+
+impl fmt::Debug for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl PartialEq for OptionsError {
+    fn eq(&self, other: &OptionsError) -> bool {
+        match (self, other) {
+            (&OptionsError::Unparseable(ref a), &OptionsError::Unparseable(ref b))
+                => a.to_string() == b.to_string(),
+            (&OptionsError::BadArgument(ref a), &OptionsError::BadArgument(ref b))
+                => a == b,
+            (&OptionsError::Useless(a1, a2, a3), &OptionsError::Useless(b1, b2, b3))
+                => a1 == b1 && a2 == b2 && a3 == b3,
+            (&OptionsError::Duplicate(a), &OptionsError::Duplicate(b))
+                => a == b,
+            _   => false,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn os(args: &[&str]) -> OptionsResult {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        Options::parse(&args)
+    }
+
+    #[test]
+    fn no_args() {
+        assert_eq!(os(&[]), OptionsResult::Ok(
+            Options {
+                flags: Flags { bytes: false, show_names: false, show_scripts: false, show_widths: false, show_base64: false, show_base32: false },
+                input_file_name: None,
+            },
+            vec![],
+        ));
+    }
+
+    #[test]
+    fn names_and_widths() {
+        assert_eq!(os(&["-nw"]), OptionsResult::Ok(
+            Options {
+                flags: Flags { bytes: false, show_names: true, show_scripts: false, show_widths: true, show_base64: false, show_base32: false },
+                input_file_name: None,
+            },
+            vec![],
+        ));
+    }
+
+    #[test]
+    fn one_file() {
+        assert_eq!(os(&["somefile.txt"]), OptionsResult::Ok(
+            Options {
+                flags: Flags { bytes: false, show_names: false, show_scripts: false, show_widths: false, show_base64: false, show_base32: false },
+                input_file_name: Some("somefile.txt".to_string()),
+            },
+            vec!["somefile.txt".to_string()],
+        ));
+    }
+
+    #[test]
+    fn bytes_alone_is_useless() {
+        assert_eq!(os(&["-b"]), OptionsResult::InvalidOptions(
+            OptionsError::Useless("bytes", true, "names, scripts, widths, base64, or base32")));
+    }
+
+    #[test]
+    fn bytes_with_names_is_fine() {
+        match os(&["-b", "-n"]) {
+            OptionsResult::Ok(..) => (),
+            other                 => panic!("expected Ok, got {:?}", other),
         }
     }
+
+    #[test]
+    fn bytes_with_base64_is_fine() {
+        match os(&["-b", "--base64"]) {
+            OptionsResult::Ok(..) => (),
+            other                 => panic!("expected Ok, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bytes_with_base32_is_fine() {
+        match os(&["-b", "--base32"]) {
+            OptionsResult::Ok(..) => (),
+            other                 => panic!("expected Ok, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn repeated_flag_is_fine_by_default() {
+        match os(&["-n", "-n"]) {
+            OptionsResult::Ok(..) => (),
+            other                 => panic!("expected Ok, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn repeated_flag_is_an_error_in_strict_mode() {
+        assert_eq!(os(&["-n", "-n", "--strict"]), OptionsResult::InvalidOptions(
+            OptionsError::Duplicate("names")));
+    }
+
+    #[test]
+    fn strict_mode_allows_non_repeated_flags() {
+        match os(&["-n", "-w", "--strict"]) {
+            OptionsResult::Ok(..) => (),
+            other                 => panic!("expected Ok, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn two_files_is_an_error() {
+        assert_eq!(os(&["one.txt", "two.txt"]), OptionsResult::InvalidOptions(
+            OptionsError::BadArgument("only one file can be given".to_string())));
+    }
+
+    #[test]
+    fn help() {
+        match os(&["--help"]) {
+            OptionsResult::Help(_) => (),
+            other                  => panic!("expected Help, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(os(&["--version"]), OptionsResult::Version);
+    }
 }
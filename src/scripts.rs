@@ -0,0 +1,163 @@
+//! Unicode script lookup.
+//!
+//! This maps a `char`'s code point to its Unicode `Script` property by
+//! walking a table of code point ranges, the same way the Unicode
+//! Character Database's `Scripts.txt` assigns one script per range. It
+//! isn't a complete implementation of that file -- just the scripts most
+//! likely to turn up when inspecting everyday text -- but it's enough to
+//! tell a reader whether a character is Latin, Greek, Han, and so on.
+
+use std::fmt;
+
+/// A Unicode script, as assigned by the Scripts property.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Script {
+    Common,
+    Inherited,
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Thai,
+    Han,
+    Hiragana,
+    Katakana,
+    Hangul,
+    Unknown,
+}
+
+impl Script {
+
+    /// Looks up the script of the given character by checking which range
+    /// in the table it falls into, returning `Script::Unknown` if it
+    /// doesn't match any of them.
+    pub fn of(c: char) -> Script {
+        let num = c as u32;
+
+        for &(lo, hi, script) in RANGES {
+            if num >= lo && num <= hi {
+                return script;
+            }
+        }
+
+        Script::Unknown
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Script::Common     => "Common",
+            Script::Inherited  => "Inherited",
+            Script::Latin      => "Latin",
+            Script::Greek      => "Greek",
+            Script::Cyrillic   => "Cyrillic",
+            Script::Armenian   => "Armenian",
+            Script::Hebrew     => "Hebrew",
+            Script::Arabic     => "Arabic",
+            Script::Devanagari => "Devanagari",
+            Script::Thai       => "Thai",
+            Script::Han        => "Han",
+            Script::Hiragana   => "Hiragana",
+            Script::Katakana   => "Katakana",
+            Script::Hangul     => "Hangul",
+            Script::Unknown    => "Unknown",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Code point ranges for each script. Ranges are checked in order, so a
+/// narrower exception (such as the combining marks living inside what
+/// would otherwise be a `Common` block) must come before the broader
+/// range it sits inside.
+static RANGES: &'static [(u32, u32, Script)] = &[
+    (0x0000, 0x0040, Script::Common),
+    (0x0041, 0x005a, Script::Latin),
+    (0x005b, 0x0060, Script::Common),
+    (0x0061, 0x007a, Script::Latin),
+    (0x007b, 0x00a9, Script::Common),
+    (0x00aa, 0x00aa, Script::Latin),
+    (0x00ab, 0x00b9, Script::Common),
+    (0x00ba, 0x00ba, Script::Latin),
+    (0x00bb, 0x00bf, Script::Common),
+    (0x00c0, 0x00d6, Script::Latin),
+    (0x00d7, 0x00d7, Script::Common),
+    (0x00d8, 0x00f6, Script::Latin),
+    (0x00f7, 0x00f7, Script::Common),
+    (0x00f8, 0x02b8, Script::Latin),
+    (0x02b9, 0x02df, Script::Common),
+    (0x0300, 0x036f, Script::Inherited),
+    (0x0370, 0x0373, Script::Greek),
+    (0x0375, 0x0377, Script::Greek),
+    (0x037a, 0x037d, Script::Greek),
+    (0x037f, 0x037f, Script::Greek),
+    (0x0384, 0x0384, Script::Greek),
+    (0x0386, 0x0386, Script::Greek),
+    (0x0388, 0x03e1, Script::Greek),
+    (0x03f0, 0x03ff, Script::Greek),
+    (0x0400, 0x0484, Script::Cyrillic),
+    (0x0487, 0x052f, Script::Cyrillic),
+    (0x0531, 0x0556, Script::Armenian),
+    (0x0559, 0x058a, Script::Armenian),
+    (0x0591, 0x05c7, Script::Hebrew),
+    (0x05d0, 0x05ea, Script::Hebrew),
+    (0x05ef, 0x05f4, Script::Hebrew),
+    (0x0600, 0x06ff, Script::Arabic),
+    (0x0750, 0x077f, Script::Arabic),
+    (0x0900, 0x097f, Script::Devanagari),
+    (0x0e00, 0x0e7f, Script::Thai),
+    (0x3041, 0x3096, Script::Hiragana),
+    (0x309d, 0x309f, Script::Hiragana),
+    (0x30a1, 0x30fa, Script::Katakana),
+    (0x30fd, 0x30ff, Script::Katakana),
+    (0x3400, 0x4dbf, Script::Han),
+    (0x4e00, 0x9fff, Script::Han),
+    (0xac00, 0xd7a3, Script::Hangul),
+    (0xf900, 0xfaff, Script::Han),
+];
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ascii_letter_is_latin() {
+        assert_eq!(Script::of('q'), Script::Latin);
+    }
+
+    #[test]
+    fn digit_is_common() {
+        assert_eq!(Script::of('7'), Script::Common);
+    }
+
+    #[test]
+    fn combining_mark_is_inherited() {
+        assert_eq!(Script::of('\u{0301}'), Script::Inherited);
+    }
+
+    #[test]
+    fn kanji_is_han() {
+        assert_eq!(Script::of('漢'), Script::Han);
+    }
+
+    #[test]
+    fn hiragana_is_hiragana() {
+        assert_eq!(Script::of('あ'), Script::Hiragana);
+    }
+
+    #[test]
+    fn cyrillic_is_cyrillic() {
+        assert_eq!(Script::of('Ж'), Script::Cyrillic);
+    }
+
+    #[test]
+    fn emoji_is_unknown() {
+        assert_eq!(Script::of('\u{1f600}'), Script::Unknown);
+    }
+}